@@ -0,0 +1,206 @@
+use serde::Serialize;
+use std::error::Error;
+
+use crate::filter;
+use crate::pow::{Target, Work};
+use crate::transaction::{Transaction, Txid};
+use crate::{
+    encode, hash_raw_transaction, read_compact_size, read_transaction, read_txid, read_u32,
+    write_compact_size,
+};
+
+const HEADER_SIZE: usize = 80;
+
+#[derive(Debug, Serialize)]
+pub struct Block {
+    pub version: u32,
+    pub prev_blockhash: Txid,
+    pub merkle_root: Txid,
+    pub time: u32,
+    pub bits: u32,
+    pub nonce: u32,
+    pub block_hash: Txid,
+    pub target: String,
+    pub difficulty: f64,
+    pub work: String,
+    pub pow_valid: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter_match: Option<bool>,
+    pub transactions: Vec<Transaction>,
+}
+
+pub fn decode_block(
+    block_hex: String,
+    verify: bool,
+    include_filter: bool,
+    match_script_hex: Option<String>,
+) -> Result<Block, Box<dyn Error>> {
+    let block_bytes =
+        hex::decode(block_hex).map_err(|e| format!("Hex decode error: {}", e))?;
+    if block_bytes.len() < HEADER_SIZE {
+        return Err(format!(
+            "Block too short: got {} bytes, need at least {} for the header",
+            block_bytes.len(),
+            HEADER_SIZE
+        )
+        .into());
+    }
+
+    let mut bytes_slice = block_bytes.as_slice();
+    let header_start = bytes_slice;
+
+    // decode the 80-byte block header
+    let version = read_u32(&mut bytes_slice)?;
+    let prev_blockhash = read_txid(&mut bytes_slice)?;
+    let merkle_root = read_txid(&mut bytes_slice)?;
+    let time = read_u32(&mut bytes_slice)?;
+    let bits = read_u32(&mut bytes_slice)?;
+    let nonce = read_u32(&mut bytes_slice)?;
+
+    let header_bytes = &header_start[..HEADER_SIZE];
+    let block_hash = hash_raw_transaction(header_bytes);
+
+    let target = Target::from_compact(bits);
+    let work = Work::from_target(&target);
+    let pow_valid = target.is_met_by(&block_hash.to_bytes());
+
+    // decode the transactions that follow the header
+    let transaction_count = read_compact_size(&mut bytes_slice)?;
+    let mut transactions = vec![];
+    for _ in 0..transaction_count {
+        transactions.push(read_transaction(&mut bytes_slice)?);
+    }
+
+    let filter_bytes = if include_filter || match_script_hex.is_some() {
+        let scripts: Vec<Vec<u8>> = transactions
+            .iter()
+            .flat_map(|transaction| transaction.outputs.iter())
+            .map(|output| hex::decode(&output.script_pubkey))
+            .collect::<Result<_, _>>()
+            .map_err(|e| format!("Hex decode error: {}", e))?;
+        Some(filter::build_block_filter(&scripts, &block_hash.to_bytes()))
+    } else {
+        None
+    };
+
+    let filter_match = match_script_hex
+        .map(|script_hex| -> Result<bool, Box<dyn Error>> {
+            let script =
+                hex::decode(script_hex).map_err(|e| format!("Hex decode error: {}", e))?;
+            filter::match_any(filter_bytes.as_ref().unwrap(), &script, &block_hash.to_bytes())
+                .map_err(|e| format!("Filter error: {}", e).into())
+        })
+        .transpose()?;
+
+    let block = Block {
+        version,
+        prev_blockhash,
+        merkle_root,
+        time,
+        bits,
+        nonce,
+        block_hash,
+        target: target.to_hex(),
+        difficulty: target.difficulty(),
+        work: work.to_hex(),
+        pow_valid,
+        filter: if include_filter {
+            filter_bytes.as_deref().map(hex::encode)
+        } else {
+            None
+        },
+        filter_match,
+        transactions,
+    };
+
+    if verify {
+        let mut re_encoded = header_bytes.to_vec();
+        write_compact_size(&mut re_encoded, block.transactions.len() as u64);
+        for transaction in &block.transactions {
+            re_encoded.extend_from_slice(&encode(transaction));
+        }
+
+        if re_encoded != block_bytes {
+            return Err(format!(
+                "Round-trip verification failed: re-encoded {} bytes, expected {}",
+                re_encoded.len(),
+                block_bytes.len()
+            )
+            .into());
+        }
+    }
+
+    Ok(block)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn header_only_block_hex() -> String {
+        let mut bytes = vec![];
+        bytes.extend_from_slice(&1_u32.to_le_bytes()); // version
+        bytes.extend_from_slice(&[0xaa_u8; 32]); // prev_blockhash
+        bytes.extend_from_slice(&[0xbb_u8; 32]); // merkle_root
+        bytes.extend_from_slice(&0_u32.to_le_bytes()); // time
+        bytes.extend_from_slice(&0x1d00ffff_u32.to_le_bytes()); // bits (difficulty 1)
+        bytes.extend_from_slice(&0_u32.to_le_bytes()); // nonce
+        bytes.push(0); // transaction count
+        hex::encode(bytes)
+    }
+
+    #[test]
+    fn test_decode_block_header_fields() -> Result<(), Box<dyn Error>> {
+        let block = decode_block(header_only_block_hex(), true, false, None)?;
+
+        assert_eq!(block.version, 1);
+        assert_eq!(block.difficulty, 1.0);
+        assert_eq!(
+            block.target,
+            "00000000ffff0000000000000000000000000000000000000000000000000000"
+        );
+        assert!(block.transactions.is_empty());
+        assert!(block.filter.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_block_with_filter_produces_empty_filter() -> Result<(), Box<dyn Error>> {
+        let block = decode_block(header_only_block_hex(), true, true, None)?;
+
+        // No transactions means no output scripts, so the filter is over an
+        // empty set and must never match anything.
+        let filter_bytes = hex::decode(block.filter.unwrap())?;
+        assert!(!filter::match_any(&filter_bytes, &[0x6a], &block.block_hash.to_bytes())?);
+
+        Ok(())
+    }
+
+    // `--match` surfaces `filter::match_any` for a caller-supplied script,
+    // without requiring `--filter` to also render the raw filter bytes.
+    #[test]
+    fn test_decode_block_match_script_reports_no_match() -> Result<(), Box<dyn Error>> {
+        let block = decode_block(
+            header_only_block_hex(),
+            true,
+            false,
+            Some(hex::encode([0x6a])),
+        )?;
+
+        assert!(block.filter.is_none());
+        assert_eq!(block.filter_match, Some(false));
+
+        Ok(())
+    }
+
+    // A truncated header must return a clean error instead of panicking on
+    // the `&header_start[..HEADER_SIZE]` slice.
+    #[test]
+    fn test_decode_block_rejects_truncated_header() {
+        let result = decode_block(hex::encode([0_u8; 4]), false, false, None);
+        assert!(result.is_err());
+    }
+}
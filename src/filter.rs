@@ -0,0 +1,188 @@
+// BIP158 Golomb-Rice-coded set (GCS) filters, as used by the "basic" block
+// filter type (0x00).
+use siphasher::sip::SipHasher13;
+use std::hash::Hasher;
+
+use crate::read_compact_size;
+use crate::write_compact_size;
+
+const P: u8 = 19;
+const M: u64 = 784931;
+
+/// Builds a BIP158 basic filter over a set of scriptPubKeys, keyed from the
+/// block hash they belong to.
+pub fn build_block_filter(scripts: &[Vec<u8>], block_hash: &[u8; 32]) -> Vec<u8> {
+    let n = scripts.len() as u64;
+    let mut hashes: Vec<u64> = scripts
+        .iter()
+        .map(|script| hash_to_range(script, block_hash, n))
+        .collect();
+    hashes.sort_unstable();
+
+    let mut writer = BitWriter::new();
+    let mut previous = 0_u64;
+    for hash in hashes {
+        golomb_rice_encode(&mut writer, hash - previous);
+        previous = hash;
+    }
+
+    let mut buffer = vec![];
+    write_compact_size(&mut buffer, n);
+    buffer.extend_from_slice(&writer.into_bytes());
+
+    buffer
+}
+
+/// Tests whether `script` is a member of a filter produced by
+/// [`build_block_filter`] for the same block hash.
+pub fn match_any(filter: &[u8], script: &[u8], block_hash: &[u8; 32]) -> Result<bool, std::io::Error> {
+    let mut slice = filter;
+    let n = read_compact_size(&mut slice)?;
+    let target = hash_to_range(script, block_hash, n);
+
+    let mut reader = BitReader::new(slice);
+    let mut running_sum = 0_u64;
+    for _ in 0..n {
+        running_sum += golomb_rice_decode(&mut reader);
+        if running_sum == target {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+fn hash_to_range(script: &[u8], block_hash: &[u8; 32], n: u64) -> u64 {
+    let mut key = [0_u8; 16];
+    key.copy_from_slice(&block_hash[..16]);
+
+    let mut hasher = SipHasher13::new_with_key(&key);
+    hasher.write(script);
+    let hash = hasher.finish();
+
+    ((hash as u128 * (n * M) as u128) >> 64) as u64
+}
+
+fn golomb_rice_encode(writer: &mut BitWriter, value: u64) {
+    let quotient = value >> P;
+    for _ in 0..quotient {
+        writer.push_bit(true);
+    }
+    writer.push_bit(false);
+
+    for i in (0..P).rev() {
+        writer.push_bit((value >> i) & 1 == 1);
+    }
+}
+
+fn golomb_rice_decode(reader: &mut BitReader) -> u64 {
+    let mut quotient = 0_u64;
+    while reader.read_bit() {
+        quotient += 1;
+    }
+
+    let mut remainder = 0_u64;
+    for _ in 0..P {
+        remainder = (remainder << 1) | reader.read_bit() as u64;
+    }
+
+    (quotient << P) | remainder
+}
+
+// Accumulates bits MSB-first into bytes.
+struct BitWriter {
+    bytes: Vec<u8>,
+    current: u8,
+    filled: u8,
+}
+
+impl BitWriter {
+    fn new() -> Self {
+        BitWriter {
+            bytes: vec![],
+            current: 0,
+            filled: 0,
+        }
+    }
+
+    fn push_bit(&mut self, bit: bool) {
+        self.current = (self.current << 1) | bit as u8;
+        self.filled += 1;
+
+        if self.filled == 8 {
+            self.bytes.push(self.current);
+            self.current = 0;
+            self.filled = 0;
+        }
+    }
+
+    fn into_bytes(mut self) -> Vec<u8> {
+        if self.filled > 0 {
+            self.current <<= 8 - self.filled;
+            self.bytes.push(self.current);
+        }
+        self.bytes
+    }
+}
+
+// Reads bits MSB-first out of a byte slice.
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    byte_index: usize,
+    bit_index: u8,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        BitReader {
+            bytes,
+            byte_index: 0,
+            bit_index: 0,
+        }
+    }
+
+    fn read_bit(&mut self) -> bool {
+        let byte = self.bytes.get(self.byte_index).copied().unwrap_or(0);
+        let bit = (byte >> (7 - self.bit_index)) & 1 == 1;
+
+        self.bit_index += 1;
+        if self.bit_index == 8 {
+            self.bit_index = 0;
+            self.byte_index += 1;
+        }
+
+        bit
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_build_block_filter_round_trip() {
+        let block_hash = [0x42_u8; 32];
+        let scripts = vec![
+            vec![0x76, 0xa9, 0x14, 0x01, 0x02, 0x03, 0x88, 0xac],
+            vec![0x00, 0x14, 0x04, 0x05, 0x06],
+            vec![0x51, 0x20, 0x07, 0x08, 0x09],
+        ];
+
+        let filter = build_block_filter(&scripts, &block_hash);
+
+        for script in &scripts {
+            assert!(match_any(&filter, script, &block_hash).unwrap());
+        }
+
+        let absent_script = vec![0xa9, 0x14, 0x0a, 0x0b, 0x0c, 0x87];
+        assert!(!match_any(&filter, &absent_script, &block_hash).unwrap());
+    }
+
+    #[test]
+    fn test_build_block_filter_empty() {
+        let block_hash = [0x01_u8; 32];
+        let filter = build_block_filter(&[], &block_hash);
+
+        assert!(!match_any(&filter, &[0x6a], &block_hash).unwrap());
+    }
+}
@@ -0,0 +1,141 @@
+use std::error::Error;
+
+use crate::block::Block;
+use crate::transaction::Transaction;
+
+/// Output format selectable via `--format`.
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum Format {
+    Json,
+    JsonCompact,
+    Csv,
+}
+
+pub fn render_transaction(
+    transaction: &Transaction,
+    format: Format,
+) -> Result<String, Box<dyn Error>> {
+    match format {
+        Format::Json => Ok(serde_json::to_string_pretty(transaction)?),
+        Format::JsonCompact => Ok(serde_json::to_string(transaction)?),
+        Format::Csv => render_csv(std::slice::from_ref(transaction)),
+    }
+}
+
+pub fn render_block(block: &Block, format: Format) -> Result<String, Box<dyn Error>> {
+    match format {
+        Format::Json => Ok(serde_json::to_string_pretty(block)?),
+        Format::JsonCompact => Ok(serde_json::to_string(block)?),
+        Format::Csv => render_csv(&block.transactions),
+    }
+}
+
+// One row per input and per output, across all given transactions. For an
+// input, `txid`/`index` identify the outpoint it spends; for an output they
+// identify the transaction and position the output belongs to.
+fn render_csv(transactions: &[Transaction]) -> Result<String, Box<dyn Error>> {
+    let mut writer = csv::Writer::from_writer(vec![]);
+    writer.write_record([
+        "record_type",
+        "txid",
+        "index",
+        "script",
+        "amount_sat",
+        "sequence",
+        "type",
+        "address",
+    ])?;
+
+    for transaction in transactions {
+        for input in &transaction.inputs {
+            writer.write_record([
+                "input",
+                &input.txid.to_string(),
+                &input.output_index.to_string(),
+                &input.script_sig,
+                "",
+                &input.sequence.to_string(),
+                "",
+                "",
+            ])?;
+        }
+
+        for (index, output) in transaction.outputs.iter().enumerate() {
+            writer.write_record([
+                "output",
+                &transaction.transaction_id.to_string(),
+                &index.to_string(),
+                &output.script_pubkey,
+                &output.amount.to_sat().to_string(),
+                "",
+                &output.script_type.to_string(),
+                output.address.as_deref().unwrap_or(""),
+            ])?;
+        }
+    }
+
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| format!("CSV writer error: {}", e))?;
+
+    Ok(String::from_utf8(bytes)?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::script::ScriptType;
+    use crate::transaction::{Amount, Input, Output, Txid};
+
+    fn sample_transaction() -> Transaction {
+        Transaction {
+            transaction_id: Txid::from_bytes([0x11; 32]),
+            wtxid: None,
+            version: 1,
+            inputs: vec![
+                Input {
+                    txid: Txid::from_bytes([0xaa; 32]),
+                    output_index: 7,
+                    script_sig: String::new(),
+                    sequence: 0xffffffff,
+                },
+                Input {
+                    txid: Txid::from_bytes([0xbb; 32]),
+                    output_index: 3,
+                    script_sig: String::new(),
+                    sequence: 0xffffffff,
+                },
+            ],
+            outputs: vec![Output {
+                amount: Amount::from_sat(1000),
+                script_pubkey: String::new(),
+                asm: String::new(),
+                script_type: ScriptType::Unknown,
+                address: None,
+            }],
+            lock_time: 0,
+            witness: None,
+        }
+    }
+
+    // Regression test: the input row's `index` column must be the spent
+    // outpoint's actual output index, not the input's position in the
+    // transaction (those happen to coincide when every input spends output 0).
+    #[test]
+    fn test_render_csv_input_row_uses_output_index() -> Result<(), Box<dyn Error>> {
+        let transaction = sample_transaction();
+        let csv = render_csv(std::slice::from_ref(&transaction))?;
+        let mut lines = csv.lines();
+
+        assert_eq!(
+            lines.next(),
+            Some("record_type,txid,index,script,amount_sat,sequence,type,address")
+        );
+
+        let rows: Vec<&str> = lines.collect();
+        assert_eq!(rows[0].split(',').nth(2), Some("7"));
+        assert_eq!(rows[1].split(',').nth(2), Some("3"));
+
+        Ok(())
+    }
+}
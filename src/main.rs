@@ -1,3 +1,8 @@
+mod block;
+mod filter;
+mod format;
+mod pow;
+mod script;
 mod transaction;
 
 use clap::Parser;
@@ -13,9 +18,29 @@ use transaction::*;
 #[command(about = "Bitcoin Transaction Decoder", long_about = None)]
 struct Cli {
     transaction_hex: String,
+
+    /// Re-encode the decoded transaction and check it matches the input bytes
+    #[arg(long)]
+    verify: bool,
+
+    /// Treat the input as a full serialized block rather than a bare transaction
+    #[arg(long)]
+    block: bool,
+
+    /// Compute a BIP158 basic block filter over the block's output scripts (requires --block)
+    #[arg(long)]
+    filter: bool,
+
+    /// Test whether a hex-encoded script is a member of the block's BIP158 filter (requires --block)
+    #[arg(long = "match", value_name = "SCRIPT_HEX")]
+    match_script: Option<String>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value = "json")]
+    format: format::Format,
 }
 
-fn read_compact_size(transaction_bytes: &mut &[u8]) -> Result<u64, IoError> {
+pub(crate) fn read_compact_size(transaction_bytes: &mut &[u8]) -> Result<u64, IoError> {
     let mut compact_size = [0_u8; 1];
     transaction_bytes.read(&mut compact_size)?;
 
@@ -40,7 +65,7 @@ fn read_compact_size(transaction_bytes: &mut &[u8]) -> Result<u64, IoError> {
 }
 
 #[allow(unused_variables)]
-fn read_u32(transaction_bytes: &mut &[u8]) -> Result<u32, IoError> {
+pub(crate) fn read_u32(transaction_bytes: &mut &[u8]) -> Result<u32, IoError> {
     let mut buffer = [0; 4];
     transaction_bytes.read(&mut buffer)?;
 
@@ -54,7 +79,7 @@ fn read_amount(transaction_bytes: &mut &[u8]) -> Result<Amount, IoError> {
     Ok(Amount::from_sat(u64::from_le_bytes(buffer)))
 }
 
-fn read_txid(transaction_bytes: &mut &[u8]) -> Result<Txid, IoError> {
+pub(crate) fn read_txid(transaction_bytes: &mut &[u8]) -> Result<Txid, IoError> {
     let mut buffer = [0; 32];
     transaction_bytes.read(&mut buffer)?;
 
@@ -69,9 +94,134 @@ fn read_script(transaction_bytes: &mut &[u8]) -> Result<String, IoError> {
     Ok(hex::encode(buffer))
 }
 
-fn hash_raw_transaction(raw_transaction: &[u8]) -> Txid {
+fn read_witness(transaction_bytes: &mut &[u8]) -> Result<Vec<String>, IoError> {
+    let item_count = read_compact_size(transaction_bytes)?;
+    let mut witness = vec![];
+
+    for _ in 0..item_count {
+        let item_size = read_compact_size(transaction_bytes)? as usize;
+        let mut buffer = vec![0_u8; item_size];
+        transaction_bytes.read(&mut buffer)?;
+
+        witness.push(hex::encode(buffer));
+    }
+
+    Ok(witness)
+}
+
+pub(crate) fn write_compact_size(buffer: &mut Vec<u8>, size: u64) {
+    match size {
+        0..=252 => buffer.push(size as u8),
+        253..=0xffff => {
+            buffer.push(253);
+            buffer.extend_from_slice(&(size as u16).to_le_bytes());
+        }
+        0x10000..=0xffffffff => {
+            buffer.push(254);
+            buffer.extend_from_slice(&(size as u32).to_le_bytes());
+        }
+        _ => {
+            buffer.push(255);
+            buffer.extend_from_slice(&size.to_le_bytes());
+        }
+    }
+}
+
+fn write_u32(buffer: &mut Vec<u8>, value: u32) {
+    buffer.extend_from_slice(&value.to_le_bytes());
+}
+
+fn write_amount(buffer: &mut Vec<u8>, amount: &Amount) {
+    buffer.extend_from_slice(&amount.to_sat().to_le_bytes());
+}
+
+fn write_txid(buffer: &mut Vec<u8>, txid: &Txid) {
+    buffer.extend_from_slice(&txid.to_bytes());
+}
+
+// A witness item is encoded exactly like a script: a compact-size length
+// followed by that many raw bytes, so `encode` also uses this for each
+// witness item.
+fn write_script(buffer: &mut Vec<u8>, script_hex: &str) {
+    let script = hex::decode(script_hex).expect("valid script hex");
+    write_compact_size(buffer, script.len() as u64);
+    buffer.extend_from_slice(&script);
+}
+
+// Re-serializes the legacy (non-witness) form of the transaction so that the
+// txid can be hashed separately from the wtxid for SegWit transactions.
+fn serialize_legacy(
+    version: u32,
+    inputs: &[Input],
+    outputs: &[Output],
+    lock_time: u32,
+) -> Vec<u8> {
+    let mut buffer = vec![];
+
+    write_u32(&mut buffer, version);
+
+    write_compact_size(&mut buffer, inputs.len() as u64);
+    for input in inputs {
+        write_txid(&mut buffer, &input.txid);
+        write_u32(&mut buffer, input.output_index);
+        write_script(&mut buffer, &input.script_sig);
+        write_u32(&mut buffer, input.sequence);
+    }
+
+    write_compact_size(&mut buffer, outputs.len() as u64);
+    for output in outputs {
+        write_amount(&mut buffer, &output.amount);
+        write_script(&mut buffer, &output.script_pubkey);
+    }
+
+    write_u32(&mut buffer, lock_time);
+
+    buffer
+}
+
+// Serializes a decoded `Transaction` back to its consensus byte encoding,
+// including the SegWit marker/flag/witness section when present.
+pub(crate) fn encode(transaction: &Transaction) -> Vec<u8> {
+    let mut buffer = vec![];
+
+    write_u32(&mut buffer, transaction.version);
+
+    if transaction.witness.is_some() {
+        buffer.push(0x00);
+        buffer.push(0x01);
+    }
+
+    write_compact_size(&mut buffer, transaction.inputs.len() as u64);
+    for input in &transaction.inputs {
+        write_txid(&mut buffer, &input.txid);
+        write_u32(&mut buffer, input.output_index);
+        write_script(&mut buffer, &input.script_sig);
+        write_u32(&mut buffer, input.sequence);
+    }
+
+    write_compact_size(&mut buffer, transaction.outputs.len() as u64);
+    for output in &transaction.outputs {
+        write_amount(&mut buffer, &output.amount);
+        write_script(&mut buffer, &output.script_pubkey);
+    }
+
+    if let Some(witness) = &transaction.witness {
+        for stack in witness {
+            write_compact_size(&mut buffer, stack.len() as u64);
+            for item in stack {
+                write_script(&mut buffer, item);
+            }
+        }
+    }
+
+    write_u32(&mut buffer, transaction.lock_time);
+
+    buffer
+}
+
+pub(crate) fn hash_raw_transaction(raw_transaction: &[u8]) -> Txid {
     let mut hasher = Sha256::new();
-    hasher.update(&raw_transaction);
+    hasher.update(raw_transaction);
     let hash1 = hasher.finalize();
 
     let mut hasher = Sha256::new();
@@ -81,23 +231,31 @@ fn hash_raw_transaction(raw_transaction: &[u8]) -> Txid {
     Txid::from_bytes(hash2.into())
 }
 
-fn decode(transaction_hex: String) -> Result<String, Box<dyn Error>> {
-    let transaction_bytes =
-        hex::decode(transaction_hex).map_err(|e| format!("Hex decode error: {}", e))?;
-    let mut bytes_slice = transaction_bytes.as_slice();
+// Parses one transaction off the front of `bytes_slice`, advancing it past
+// everything consumed. Used both for a bare transaction and for each
+// transaction inside a block.
+pub(crate) fn read_transaction(bytes_slice: &mut &[u8]) -> Result<Transaction, Box<dyn Error>> {
+    let transaction_start = *bytes_slice;
 
     // decode version
-    let version = read_u32(&mut bytes_slice)?;
+    let version = read_u32(bytes_slice)?;
+
+    // detect the SegWit marker (0x00) and flag (0x01) that precede the
+    // input count in BIP141 transactions
+    let is_segwit = bytes_slice.starts_with(&[0x00, 0x01]);
+    if is_segwit {
+        *bytes_slice = &bytes_slice[2..];
+    }
 
     // decode inputs
-    let input_count = read_compact_size(&mut bytes_slice)?;
+    let input_count = read_compact_size(bytes_slice)?;
     let mut inputs = vec![];
 
     for _ in 0..input_count {
-        let txid = read_txid(&mut bytes_slice)?;
-        let output_index = read_u32(&mut bytes_slice)?;
-        let script_sig = read_script(&mut bytes_slice)?;
-        let sequence = read_u32(&mut bytes_slice)?;
+        let txid = read_txid(bytes_slice)?;
+        let output_index = read_u32(bytes_slice)?;
+        let script_sig = read_script(bytes_slice)?;
+        let sequence = read_u32(bytes_slice)?;
 
         inputs.push(Input {
             txid,
@@ -108,46 +266,108 @@ fn decode(transaction_hex: String) -> Result<String, Box<dyn Error>> {
     }
 
     // decode outputs
-    let output_count = read_compact_size(&mut bytes_slice)?;
+    let output_count = read_compact_size(bytes_slice)?;
     let mut outputs = vec![];
 
     for _ in 0..output_count {
-        let amount = read_amount(&mut bytes_slice)?;
-        let script_pubkey = read_script(&mut bytes_slice)?;
+        let amount = read_amount(bytes_slice)?;
+        let script_pubkey = read_script(bytes_slice)?;
+        let script_bytes =
+            hex::decode(&script_pubkey).map_err(|e| format!("Hex decode error: {}", e))?;
+
+        let asm = script::disassemble(&script_bytes);
+        let script_type = script::classify(&script_bytes);
+        let address = script::derive_address(script_type, &script_bytes);
 
         outputs.push(Output {
             amount,
             script_pubkey,
+            asm,
+            script_type,
+            address,
         });
     }
 
+    // decode witness stacks, one per input, only present for SegWit transactions
+    let witness = if is_segwit {
+        let mut witnesses = vec![];
+        for _ in 0..input_count {
+            witnesses.push(read_witness(bytes_slice)?);
+        }
+        Some(witnesses)
+    } else {
+        None
+    };
+
     // decode locktime
-    let lock_time = read_u32(&mut bytes_slice)?;
-    let transaction_id = hash_raw_transaction(&transaction_bytes);
+    let lock_time = read_u32(bytes_slice)?;
+
+    // the txid always excludes the marker/flag/witness, so SegWit transactions
+    // need to be re-serialized in their legacy form before hashing
+    let raw_transaction = &transaction_start[..transaction_start.len() - bytes_slice.len()];
+    let (transaction_id, wtxid) = if is_segwit {
+        let legacy_bytes = serialize_legacy(version, &inputs, &outputs, lock_time);
+        (
+            hash_raw_transaction(&legacy_bytes),
+            Some(hash_raw_transaction(raw_transaction)),
+        )
+    } else {
+        (hash_raw_transaction(raw_transaction), None)
+    };
 
     // initialise decoded transaction
-    let transaction = Transaction {
+    Ok(Transaction {
         transaction_id,
+        wtxid,
         version,
         inputs,
         outputs,
         lock_time,
-    };
+        witness,
+    })
+}
+
+fn decode(transaction_hex: String, verify: bool) -> Result<Transaction, Box<dyn Error>> {
+    let transaction_bytes =
+        hex::decode(transaction_hex).map_err(|e| format!("Hex decode error: {}", e))?;
+    let mut bytes_slice = transaction_bytes.as_slice();
+    let transaction = read_transaction(&mut bytes_slice)?;
+
+    if verify {
+        let re_encoded = encode(&transaction);
+        if re_encoded != transaction_bytes {
+            return Err(format!(
+                "Round-trip verification failed: re-encoded {} bytes, expected {}",
+                re_encoded.len(),
+                transaction_bytes.len()
+            )
+            .into());
+        }
+    }
 
-    Ok(serde_json::to_string_pretty(&transaction)?)
+    Ok(transaction)
 }
 
 fn main() {
     let cli = Cli::parse();
 
-    match decode(cli.transaction_hex) {
-        Ok(json) => println!("{}", json),
+    let result = if cli.block {
+        block::decode_block(cli.transaction_hex, cli.verify, cli.filter, cli.match_script)
+            .and_then(|block| format::render_block(&block, cli.format))
+    } else {
+        decode(cli.transaction_hex, cli.verify)
+            .and_then(|transaction| format::render_transaction(&transaction, cli.format))
+    };
+
+    match result {
+        Ok(output) => println!("{}", output),
         Err(e) => println!("{}", e),
     }
 }
 
 #[cfg(test)]
 mod test {
+    use super::decode;
     use super::read_compact_size;
     use super::Error;
 
@@ -178,4 +398,103 @@ mod test {
 
         Ok(())
     }
+
+    // Regression test for a bug where `write_txid` reversed the prevout txid
+    // bytes a second time, corrupting both `--verify` and the SegWit txid
+    // (which is hashed from a re-serialization that goes through `write_txid`).
+    // A palindromic txid can't catch this, so this one counts 1..=32.
+    #[test]
+    fn test_segwit_round_trip_with_nonpalindromic_txid() -> Result<(), Box<dyn Error>> {
+        let prevout_txid: Vec<u8> = (1_u8..=32).collect();
+
+        let mut tx_bytes = vec![];
+        tx_bytes.extend_from_slice(&1_u32.to_le_bytes()); // version
+        tx_bytes.extend_from_slice(&[0x00, 0x01]); // segwit marker, flag
+        tx_bytes.push(1); // input count
+        tx_bytes.extend_from_slice(&prevout_txid);
+        tx_bytes.extend_from_slice(&0_u32.to_le_bytes()); // output_index
+        tx_bytes.push(0); // empty scriptSig
+        tx_bytes.extend_from_slice(&0xffffffff_u32.to_le_bytes()); // sequence
+        tx_bytes.push(1); // output count
+        tx_bytes.extend_from_slice(&1000_u64.to_le_bytes()); // amount
+        tx_bytes.push(2); // script length
+        tx_bytes.extend_from_slice(&[0x51, 0x51]);
+        tx_bytes.push(1); // witness item count
+        tx_bytes.push(3); // item length
+        tx_bytes.extend_from_slice(&[0x01, 0x02, 0x03]);
+        tx_bytes.extend_from_slice(&0_u32.to_le_bytes()); // lock_time
+
+        // `decode(.., verify: true)` re-encodes the transaction and checks it
+        // matches `tx_bytes` byte for byte, so the corrupted write_txid alone
+        // would make this `?` fail.
+        let transaction = decode(hex::encode(&tx_bytes), true)?;
+
+        let mut expected_display_bytes = prevout_txid;
+        expected_display_bytes.reverse();
+        assert_eq!(
+            transaction.inputs[0].txid.to_string(),
+            hex::encode(expected_display_bytes)
+        );
+
+        assert_ne!(
+            transaction.transaction_id.to_string(),
+            transaction.wtxid.unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    fn legacy_transaction_bytes() -> Vec<u8> {
+        let mut tx_bytes = vec![];
+        tx_bytes.extend_from_slice(&1_u32.to_le_bytes()); // version
+        tx_bytes.push(1); // input count
+        tx_bytes.extend_from_slice(&[0xaa_u8; 32]); // prevout txid
+        tx_bytes.extend_from_slice(&0_u32.to_le_bytes()); // output_index
+        tx_bytes.push(0); // empty scriptSig
+        tx_bytes.extend_from_slice(&0xffffffff_u32.to_le_bytes()); // sequence
+        tx_bytes.push(1); // output count
+        tx_bytes.extend_from_slice(&1000_u64.to_le_bytes()); // amount
+        tx_bytes.push(0); // empty scriptPubKey
+        tx_bytes.extend_from_slice(&0_u32.to_le_bytes()); // lock_time
+        tx_bytes
+    }
+
+    // A transaction with no SegWit marker/flag byte pair right after the
+    // version has no witness data and reports no separate wtxid.
+    #[test]
+    fn test_legacy_transaction_has_no_wtxid() -> Result<(), Box<dyn Error>> {
+        let transaction = decode(hex::encode(legacy_transaction_bytes()), true)?;
+
+        assert!(transaction.wtxid.is_none());
+        assert!(transaction.witness.is_none());
+
+        Ok(())
+    }
+
+    // Inserting the 0x00 0x01 marker/flag pair right after the version must
+    // be detected as SegWit, producing a distinct wtxid (hashed including the
+    // witness data) from the txid (hashed excluding it).
+    #[test]
+    fn test_segwit_marker_and_flag_are_detected() -> Result<(), Box<dyn Error>> {
+        let legacy_bytes = legacy_transaction_bytes();
+
+        let mut segwit_bytes = vec![];
+        segwit_bytes.extend_from_slice(&legacy_bytes[..4]); // version
+        segwit_bytes.extend_from_slice(&[0x00, 0x01]); // segwit marker, flag
+        segwit_bytes.extend_from_slice(&legacy_bytes[4..legacy_bytes.len() - 4]); // inputs/outputs
+        segwit_bytes.push(1); // witness item count
+        segwit_bytes.push(1); // item length
+        segwit_bytes.push(0x2a);
+        segwit_bytes.extend_from_slice(&legacy_bytes[legacy_bytes.len() - 4..]); // lock_time
+
+        let transaction = decode(hex::encode(segwit_bytes), true)?;
+
+        assert_eq!(transaction.witness, Some(vec![vec!["2a".to_string()]]));
+        assert_ne!(
+            transaction.transaction_id.to_string(),
+            transaction.wtxid.unwrap().to_string()
+        );
+
+        Ok(())
+    }
 }
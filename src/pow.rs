@@ -0,0 +1,116 @@
+// Difficulty target and cumulative work, derived from a block header's
+// compact `bits` field (BIP... well, pre-BIP consensus rules).
+use num_bigint::BigUint;
+
+// The highest possible target, corresponding to difficulty 1 (bits 0x1d00ffff).
+const MAX_TARGET_BITS: u32 = 0x1d00ffff;
+
+/// A 256-bit proof-of-work target, expanded from a block header's compact
+/// `bits` field. Exposes only the conversions the decoder needs, not a
+/// general big-integer API.
+#[derive(Debug, Clone)]
+pub struct Target(BigUint);
+
+impl Target {
+    /// Expands the compact `bits` encoding: the low 23 bits are the mantissa
+    /// (bit 23, the sign bit, is dropped), the high byte is the exponent, and
+    /// `target = mantissa << (8*(exponent-3))`.
+    pub fn from_compact(bits: u32) -> Self {
+        let exponent = bits >> 24;
+        let mantissa = BigUint::from(bits & 0x007f_ffff);
+
+        let target = if exponent <= 3 {
+            mantissa >> (8 * (3 - exponent)) as usize
+        } else {
+            mantissa << (8 * (exponent - 3)) as usize
+        };
+
+        Target(target)
+    }
+
+    /// Whether a header hash (in internal, little-endian byte order) satisfies
+    /// this target.
+    pub fn is_met_by(&self, header_hash: &[u8; 32]) -> bool {
+        BigUint::from_bytes_le(header_hash) <= self.0
+    }
+
+    /// The ratio of the maximum (difficulty-1) target to this one.
+    pub fn difficulty(&self) -> f64 {
+        let max_target = Target::from_compact(MAX_TARGET_BITS).0;
+        ratio_as_f64(&max_target, &self.0)
+    }
+
+    pub fn to_hex(&self) -> String {
+        format!("{:0>64}", self.0.to_str_radix(16))
+    }
+}
+
+/// The accumulated proof-of-work behind a target, `(2^256) / (target + 1)`.
+#[derive(Debug, Clone)]
+pub struct Work(BigUint);
+
+impl Work {
+    pub fn from_target(target: &Target) -> Self {
+        let numerator = BigUint::from(1_u8) << 256;
+        Work(numerator / (&target.0 + BigUint::from(1_u8)))
+    }
+
+    pub fn to_hex(&self) -> String {
+        format!("{:0>64}", self.0.to_str_radix(16))
+    }
+}
+
+// Computes numerator/denominator as an f64 by shifting both operands right
+// until the denominator fits in 52 bits, then converting each to f64.
+fn ratio_as_f64(numerator: &BigUint, denominator: &BigUint) -> f64 {
+    let shift = denominator.bits().saturating_sub(52);
+    let scaled_numerator: BigUint = numerator >> shift;
+    let scaled_denominator: BigUint = denominator >> shift;
+
+    let numerator_f64 = scaled_numerator.to_string().parse::<f64>().unwrap_or(0.0);
+    let denominator_f64 = scaled_denominator
+        .to_string()
+        .parse::<f64>()
+        .unwrap_or(1.0);
+
+    numerator_f64 / denominator_f64
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_target_from_compact_at_max_target() {
+        let target = Target::from_compact(MAX_TARGET_BITS);
+
+        assert_eq!(
+            target.to_hex(),
+            "00000000ffff0000000000000000000000000000000000000000000000000000"
+        );
+        assert_eq!(target.difficulty(), 1.0);
+    }
+
+    #[test]
+    fn test_work_from_target_at_max_target() {
+        let target = Target::from_compact(MAX_TARGET_BITS);
+        let work = Work::from_target(&target);
+
+        assert_eq!(
+            work.to_hex(),
+            "0000000000000000000000000000000000000000000000000000000100010001"
+        );
+    }
+
+    #[test]
+    fn test_is_met_by() {
+        let target = Target::from_compact(MAX_TARGET_BITS);
+
+        let met = [0_u8; 32];
+        assert!(target.is_met_by(&met));
+
+        let mut not_met = [0_u8; 32];
+        not_met[31] = 0xff;
+        assert!(!target.is_met_by(&not_met));
+    }
+}
@@ -0,0 +1,150 @@
+use bech32::{ToBase32, Variant};
+use serde::{Serialize, Serializer};
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+/// Standard scriptPubKey shapes that `classify` recognises.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptType {
+    P2pkh,
+    P2sh,
+    P2wpkh,
+    P2wsh,
+    P2tr,
+    Unknown,
+}
+
+impl fmt::Display for ScriptType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            ScriptType::P2pkh => "p2pkh",
+            ScriptType::P2sh => "p2sh",
+            ScriptType::P2wpkh => "p2wpkh",
+            ScriptType::P2wsh => "p2wsh",
+            ScriptType::P2tr => "p2tr",
+            ScriptType::Unknown => "unknown",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl Serialize for ScriptType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+/// Classifies a scriptPubKey as one of the standard output types, based on
+/// its byte-for-byte template rather than a generic script interpreter.
+pub fn classify(script_pubkey: &[u8]) -> ScriptType {
+    match script_pubkey {
+        [0x76, 0xa9, 0x14, .., 0x88, 0xac] if script_pubkey.len() == 25 => ScriptType::P2pkh,
+        [0xa9, 0x14, .., 0x87] if script_pubkey.len() == 23 => ScriptType::P2sh,
+        [0x00, 0x14, ..] if script_pubkey.len() == 22 => ScriptType::P2wpkh,
+        [0x00, 0x20, ..] if script_pubkey.len() == 34 => ScriptType::P2wsh,
+        [0x51, 0x20, ..] if script_pubkey.len() == 34 => ScriptType::P2tr,
+        _ => ScriptType::Unknown,
+    }
+}
+
+/// Derives the mainnet address for a recognised scriptPubKey, or `None` for
+/// anything `classify` couldn't place.
+pub fn derive_address(script_type: ScriptType, script_pubkey: &[u8]) -> Option<String> {
+    match script_type {
+        ScriptType::P2pkh => Some(base58check(0x00, &script_pubkey[3..23])),
+        ScriptType::P2sh => Some(base58check(0x05, &script_pubkey[2..22])),
+        ScriptType::P2wpkh => segwit_address(0, &script_pubkey[2..22]),
+        ScriptType::P2wsh => segwit_address(0, &script_pubkey[2..34]),
+        ScriptType::P2tr => segwit_address(1, &script_pubkey[2..34]),
+        ScriptType::Unknown => None,
+    }
+}
+
+fn base58check(version: u8, payload: &[u8]) -> String {
+    let mut data = vec![version];
+    data.extend_from_slice(payload);
+
+    let checksum = double_sha256(&data);
+    data.extend_from_slice(&checksum[..4]);
+
+    bs58::encode(data).into_string()
+}
+
+fn segwit_address(witness_version: u8, program: &[u8]) -> Option<String> {
+    let mut data = vec![bech32::u5::try_from_u8(witness_version).ok()?];
+    data.extend(program.to_base32());
+
+    let variant = if witness_version == 0 {
+        Variant::Bech32
+    } else {
+        Variant::Bech32m
+    };
+
+    bech32::encode("bc", data, variant).ok()
+}
+
+fn double_sha256(data: &[u8]) -> [u8; 32] {
+    let hash1 = Sha256::digest(data);
+    Sha256::digest(hash1).into()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_classify_and_derive_address_known_vectors() {
+        let mut p2pkh = vec![0x76, 0xa9, 0x14];
+        p2pkh.extend_from_slice(&[0_u8; 20]);
+        p2pkh.extend_from_slice(&[0x88, 0xac]);
+        let script_type = classify(&p2pkh);
+        assert_eq!(script_type, ScriptType::P2pkh);
+        assert_eq!(
+            derive_address(script_type, &p2pkh).as_deref(),
+            Some("1111111111111111111114oLvT2")
+        );
+
+        let mut p2wpkh = vec![0x00, 0x14];
+        p2wpkh.extend_from_slice(&[0_u8; 20]);
+        let script_type = classify(&p2wpkh);
+        assert_eq!(script_type, ScriptType::P2wpkh);
+        assert_eq!(
+            derive_address(script_type, &p2wpkh).as_deref(),
+            Some("bc1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq9e75rs")
+        );
+
+        let mut p2tr = vec![0x51, 0x20];
+        p2tr.extend_from_slice(&[0_u8; 32]);
+        let script_type = classify(&p2tr);
+        assert_eq!(script_type, ScriptType::P2tr);
+        assert_eq!(
+            derive_address(script_type, &p2tr).as_deref(),
+            Some("bc1pqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqpqqenm")
+        );
+
+        let mut p2sh = vec![0xa9, 0x14];
+        p2sh.extend_from_slice(&[0_u8; 20]);
+        p2sh.push(0x87);
+        let script_type = classify(&p2sh);
+        assert_eq!(script_type, ScriptType::P2sh);
+        assert_eq!(
+            derive_address(script_type, &p2sh).as_deref(),
+            Some("31h1vYVSYuKP6AhS86fbRdMw9XHieotbST")
+        );
+
+        let mut p2wsh = vec![0x00, 0x20];
+        p2wsh.extend_from_slice(&[0_u8; 32]);
+        let script_type = classify(&p2wsh);
+        assert_eq!(script_type, ScriptType::P2wsh);
+        assert_eq!(
+            derive_address(script_type, &p2wsh).as_deref(),
+            Some("bc1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqthqst8")
+        );
+
+        assert_eq!(classify(&[0x6a]), ScriptType::Unknown);
+        assert_eq!(derive_address(ScriptType::Unknown, &[0x6a]), None);
+    }
+}
@@ -0,0 +1,82 @@
+mod address;
+mod opcodes;
+
+pub use address::{classify, derive_address, ScriptType};
+
+/// Renders a raw script as human-readable ASM: pushdata opcodes become their
+/// hex operand, everything else is looked up by name.
+pub fn disassemble(script: &[u8]) -> String {
+    let mut tokens = vec![];
+    let mut cursor = 0;
+
+    while cursor < script.len() {
+        let opcode = script[cursor];
+        cursor += 1;
+
+        let push_len = match opcode {
+            0x01..=0x4b => Some(opcode as usize),
+            0x4c => read_pushdata_len(script, &mut cursor, 1),
+            0x4d => read_pushdata_len(script, &mut cursor, 2),
+            0x4e => read_pushdata_len(script, &mut cursor, 4),
+            _ => None,
+        };
+
+        match push_len {
+            Some(len) if cursor + len <= script.len() => {
+                tokens.push(hex::encode(&script[cursor..cursor + len]));
+                cursor += len;
+            }
+            Some(_) => break, // truncated pushdata, nothing sensible left to render
+            None => tokens.push(opcodes::name(opcode)),
+        }
+    }
+
+    tokens.join(" ")
+}
+
+// Reads the length prefix for OP_PUSHDATA1/2/4 and advances `cursor` past it.
+fn read_pushdata_len(script: &[u8], cursor: &mut usize, len_bytes: usize) -> Option<usize> {
+    if *cursor + len_bytes > script.len() {
+        return None;
+    }
+
+    let mut buffer = [0_u8; 4];
+    buffer[..len_bytes].copy_from_slice(&script[*cursor..*cursor + len_bytes]);
+    *cursor += len_bytes;
+
+    Some(u32::from_le_bytes(buffer) as usize)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_disassemble_direct_pushdata_and_opcode() {
+        // OP_DUP OP_HASH160 <3-byte push> OP_EQUALVERIFY OP_CHECKSIG
+        let script = [0x76, 0xa9, 0x03, 0x01, 0x02, 0x03, 0x88, 0xac];
+        assert_eq!(disassemble(&script), "OP_DUP OP_HASH160 010203 OP_EQUALVERIFY OP_CHECKSIG");
+    }
+
+    #[test]
+    fn test_disassemble_op_pushdata1() {
+        // OP_PUSHDATA1 <1-byte length = 2> <2 bytes>
+        let script = [0x4c, 0x02, 0xaa, 0xbb];
+        assert_eq!(disassemble(&script), "aabb");
+    }
+
+    #[test]
+    fn test_disassemble_op_pushdata2() {
+        // OP_PUSHDATA2 <2-byte little-endian length = 2> <2 bytes>
+        let script = [0x4d, 0x02, 0x00, 0xaa, 0xbb];
+        assert_eq!(disassemble(&script), "aabb");
+    }
+
+    #[test]
+    fn test_disassemble_truncated_pushdata_stops_cleanly() {
+        // Pushdata claims 5 bytes but only 2 remain; disassembly should stop
+        // without panicking and without emitting a partial token.
+        let script = [0x05, 0xaa, 0xbb];
+        assert_eq!(disassemble(&script), "");
+    }
+}
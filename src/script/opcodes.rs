@@ -0,0 +1,34 @@
+// Names for the opcodes that show up in standard scriptPubKeys/scriptSigs.
+// This is not an exhaustive table of the Bitcoin Script opcode set, just the
+// ones `disassemble` needs to render recognisable ASM.
+pub fn name(opcode: u8) -> String {
+    match opcode {
+        0x00 => "OP_0".to_string(),
+        0x4c => "OP_PUSHDATA1".to_string(),
+        0x4d => "OP_PUSHDATA2".to_string(),
+        0x4e => "OP_PUSHDATA4".to_string(),
+        0x4f => "OP_1NEGATE".to_string(),
+        0x51..=0x60 => format!("OP_{}", opcode - 0x50),
+        0x61 => "OP_NOP".to_string(),
+        0x63 => "OP_IF".to_string(),
+        0x64 => "OP_NOTIF".to_string(),
+        0x67 => "OP_ELSE".to_string(),
+        0x68 => "OP_ENDIF".to_string(),
+        0x69 => "OP_VERIFY".to_string(),
+        0x6a => "OP_RETURN".to_string(),
+        0x6e => "OP_2DUP".to_string(),
+        0x76 => "OP_DUP".to_string(),
+        0x87 => "OP_EQUAL".to_string(),
+        0x88 => "OP_EQUALVERIFY".to_string(),
+        0xa6 => "OP_RIPEMD160".to_string(),
+        0xa7 => "OP_SHA1".to_string(),
+        0xa8 => "OP_SHA256".to_string(),
+        0xa9 => "OP_HASH160".to_string(),
+        0xaa => "OP_HASH256".to_string(),
+        0xac => "OP_CHECKSIG".to_string(),
+        0xad => "OP_CHECKSIGVERIFY".to_string(),
+        0xae => "OP_CHECKMULTISIG".to_string(),
+        0xaf => "OP_CHECKMULTISIGVERIFY".to_string(),
+        _ => format!("OP_UNKNOWN({opcode:#04x})"),
+    }
+}
@@ -0,0 +1,92 @@
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+use crate::script::ScriptType;
+
+#[derive(Debug)]
+pub struct Amount(u64);
+
+impl Amount {
+    pub fn from_sat(satoshi: u64) -> Self {
+        Amount(satoshi)
+    }
+
+    pub fn to_sat(&self) -> u64 {
+        self.0
+    }
+
+    pub fn to_btc(&self) -> f64 {
+        self.0 as f64 / 100_000_000.0
+    }
+}
+
+impl Serialize for Amount {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_f64(self.to_btc())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Txid([u8; 32]);
+
+impl Txid {
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Txid(bytes)
+    }
+
+    pub fn to_bytes(self) -> [u8; 32] {
+        self.0
+    }
+}
+
+impl fmt::Display for Txid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut bytes = self.0;
+        bytes.reverse();
+        write!(f, "{}", hex::encode(bytes))
+    }
+}
+
+impl Serialize for Txid {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Input {
+    pub txid: Txid,
+    pub output_index: u32,
+    pub script_sig: String,
+    pub sequence: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Output {
+    pub amount: Amount,
+    pub script_pubkey: String,
+    pub asm: String,
+    #[serde(rename = "type")]
+    pub script_type: ScriptType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Transaction {
+    pub transaction_id: Txid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wtxid: Option<Txid>,
+    pub version: u32,
+    pub inputs: Vec<Input>,
+    pub outputs: Vec<Output>,
+    pub lock_time: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub witness: Option<Vec<Vec<String>>>,
+}